@@ -28,6 +28,175 @@ pub enum lcr_dilithium_type {
 	lcr_dilithium_87,
 }
 
+/// Hash algorithm used for the HashML-DSA pre-hash variant
+///
+/// The selected algorithm identifies the OID that is signed together
+/// with the message digest as defined by FIPS 204.
+pub enum lcr_dilithium_hash_alg {
+	lcr_dilithium_hash_sha256,
+	lcr_dilithium_hash_sha512,
+	lcr_dilithium_hash_shake128,
+	lcr_dilithium_hash_shake256,
+}
+
+/// Leancrypto wrapper for lc_dilithium_ctx
+///
+/// The context allows a caller to supply a domain-separating context
+/// string (see FIPS 204 section 5.2), to select the HashML-DSA
+/// pre-hash variant, and to perform streaming sign/verify operations
+/// so that a message does not have to be buffered in full.
+pub struct lcr_dilithium_ctx {
+	ctx: *mut leancrypto::lc_dilithium_ctx,
+}
+
+#[allow(dead_code)]
+impl lcr_dilithium_ctx {
+	/// Allocate a new Dilithium context
+	pub fn new() -> Result<Self, SignatureError> {
+		let mut ctx: *mut leancrypto::lc_dilithium_ctx = ptr::null_mut();
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ctx_alloc(&mut ctx)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(lcr_dilithium_ctx { ctx })
+	}
+
+	/// Set the domain-separation context string
+	///
+	/// [context] holds the context octet string, 0 to 255 bytes long
+	pub fn set_context(&mut self, context: &[u8]) ->
+		Result<(), SignatureError> {
+		if context.len() > 255 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ctx_set_context(
+				self.ctx, context.as_ptr(), context.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	fn lcr_dilithium_hash_alg_mapping(hash_alg: lcr_dilithium_hash_alg) ->
+		*const leancrypto::lc_hash {
+		match hash_alg {
+			lcr_dilithium_hash_alg::lcr_dilithium_hash_sha256 =>
+				unsafe { &leancrypto::lc_sha256 },
+			lcr_dilithium_hash_alg::lcr_dilithium_hash_sha512 =>
+				unsafe { &leancrypto::lc_sha512 },
+			lcr_dilithium_hash_alg::lcr_dilithium_hash_shake128 =>
+				unsafe { &leancrypto::lc_shake128 },
+			lcr_dilithium_hash_alg::lcr_dilithium_hash_shake256 =>
+				unsafe { &leancrypto::lc_shake256 },
+		}
+	}
+
+	/// Switch the context into HashML-DSA mode
+	///
+	/// Once set, [lcr_dilithium::sign_ctx] / [lcr_dilithium::verify_ctx]
+	/// and the streaming variants interpret their message argument as
+	/// the already-computed digest and sign/verify the OID of
+	/// [hash_alg] together with that digest instead of the raw message.
+	pub fn set_hash(&mut self, hash_alg: lcr_dilithium_hash_alg) ->
+		Result<(), SignatureError> {
+		let hash = Self::lcr_dilithium_hash_alg_mapping(hash_alg);
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ctx_set_hash(self.ctx, hash)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+}
+
+/// This ensures the context is always released and its state
+/// zeroized regardless of when it goes out of scope
+impl Drop for lcr_dilithium_ctx {
+	fn drop(&mut self) {
+		if !self.ctx.is_null() {
+			unsafe { leancrypto::lc_dilithium_ctx_zero_free(self.ctx) };
+			self.ctx = ptr::null_mut();
+		}
+		atomic::compiler_fence(atomic::Ordering::SeqCst);
+	}
+}
+
+/// Abstraction over a leancrypto RNG context
+///
+/// This lets [lcr_dilithium::sign_with_rng] accept any caller-supplied
+/// RNG handle - fully deterministic, seeded, or system-entropy -
+/// instead of hardcoding `lc_seeded_rng`.
+/// [lcr_dilithium::keypair_from_seed] takes a seed rather than an RNG
+/// handle and always uses a [lcr_seeded_drbg].
+pub trait lcr_rng {
+	/// Raw leancrypto RNG context to pass to the C API
+	fn rng_ctx(&self) -> *mut leancrypto::lc_rng_ctx;
+}
+
+/// A leancrypto DRBG seeded from a caller-supplied seed
+///
+/// Used to make key generation and signing reproducible from a fixed
+/// seed, e.g. for known-answer tests or deterministic deployments.
+pub struct lcr_seeded_drbg {
+	rng: *mut leancrypto::lc_rng_ctx,
+}
+
+#[allow(dead_code)]
+impl lcr_seeded_drbg {
+	/// Allocate a DRBG and seed it with [seed]
+	pub fn new(seed: &[u8]) -> Result<Self, SignatureError> {
+		let mut rng: *mut leancrypto::lc_rng_ctx = ptr::null_mut();
+
+		let result = unsafe {
+			leancrypto::lc_rng_alloc(&mut rng,
+						  &leancrypto::lc_hash_drbg)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_rng_seed(rng, seed.as_ptr(), seed.len(),
+						 ptr::null(), 0)
+		};
+		if result < 0 {
+			unsafe { leancrypto::lc_rng_zero_free(rng) };
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(lcr_seeded_drbg { rng })
+	}
+}
+
+impl lcr_rng for lcr_seeded_drbg {
+	fn rng_ctx(&self) -> *mut leancrypto::lc_rng_ctx {
+		self.rng
+	}
+}
+
+/// This ensures the DRBG state is always released and zeroized
+/// regardless of when it goes out of scope
+impl Drop for lcr_seeded_drbg {
+	fn drop(&mut self) {
+		if !self.rng.is_null() {
+			unsafe { leancrypto::lc_rng_zero_free(self.rng) };
+			self.rng = ptr::null_mut();
+		}
+		atomic::compiler_fence(atomic::Ordering::SeqCst);
+	}
+}
+
 /// Leancrypto wrapper for lc_dilithium
 pub struct lcr_dilithium {
 	// Context
@@ -158,6 +327,30 @@ impl lcr_dilithium {
 		Ok(())
 	}
 
+	/// Generate a Dilithium / ML-DSA key pair deterministically from a
+	/// caller-supplied seed
+	///
+	/// [dilithium_type] key type
+	/// [seed] seed material fed into a leancrypto DRBG used as the RNG
+	pub fn keypair_from_seed(&mut self, dilithium_type: lcr_dilithium_type,
+				  seed: &[u8]) -> Result<(), SignatureError> {
+		let drbg = lcr_seeded_drbg::new(seed)?;
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_keypair(
+				&mut self.pk, &mut self.sk, drbg.rng_ctx(),
+				Self::lcr_dilithium_type_mapping(dilithium_type))
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sk_set = true;
+		self.pk_set = true;
+
+		Ok(())
+	}
+
 	/// Sign message with pure signature operation
 	///
 	/// [msg] holds the message to be signed
@@ -203,6 +396,35 @@ impl lcr_dilithium {
 		Ok(())
 	}
 
+	/// Sign message using a caller-supplied RNG
+	///
+	/// This generalizes [Self::sign] / [Self::sign_deterministic] into
+	/// an explicit RNG-injection point: pass a [lcr_seeded_drbg] for
+	/// reproducible signatures, or any other [lcr_rng] implementation
+	/// for system entropy.
+	///
+	/// [msg] holds the message to be signed
+	/// [rng] RNG handle to use for the randomized signing operation
+	pub fn sign_with_rng<R: lcr_rng>(&mut self, msg: &[u8], rng: &R) ->
+		Result<(), SignatureError> {
+		if self.sk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sign(
+				&mut self.sig, msg.as_ptr(), msg.len(),
+				&self.sk, rng.rng_ctx())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sig_set = true;
+
+		Ok(())
+	}
+
 	/// Verify message with pure signature operation
 	///
 	/// [msg] holds the message to be verified
@@ -226,6 +448,175 @@ impl lcr_dilithium {
 		Ok(())
 	}
 
+	/// Sign message with context string and/or HashML-DSA pre-hash
+	///
+	/// [msg] holds the message (or, in HashML-DSA mode, the digest)
+	/// [ctx] holds the context configured via [lcr_dilithium_ctx]
+	pub fn sign_ctx(&mut self, msg: &[u8], ctx: &mut lcr_dilithium_ctx) ->
+		Result<(), SignatureError> {
+		if self.sk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sign_ctx(
+				&mut self.sig, ctx.ctx, msg.as_ptr(), msg.len(),
+				&self.sk, leancrypto::lc_seeded_rng)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sig_set = true;
+
+		Ok(())
+	}
+
+	/// Verify message with context string and/or HashML-DSA pre-hash
+	///
+	/// [msg] holds the message (or, in HashML-DSA mode, the digest)
+	/// [ctx] holds the context configured via [lcr_dilithium_ctx]
+	pub fn verify_ctx(&mut self, msg: &[u8], ctx: &mut lcr_dilithium_ctx) ->
+		Result<(), SignatureError> {
+		if self.pk_set == false || self.sig_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_verify_ctx(
+				&mut self.sig, ctx.ctx, msg.as_ptr(), msg.len(),
+				&self.pk)
+		};
+		if result == -1*(leancrypto::EBADMSG as i32) {
+			return Err(SignatureError::VerificationError);
+		}
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	/// Initialize a streaming signature operation
+	///
+	/// [ctx] holds the context used to carry the stream state
+	pub fn sign_init(&mut self, ctx: &mut lcr_dilithium_ctx) ->
+		Result<(), SignatureError> {
+		if self.sk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sign_init(ctx.ctx, &self.sk)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	/// Feed another chunk of the message into a streaming signature
+	/// operation
+	///
+	/// [ctx] holds the context previously passed to [Self::sign_init]
+	/// [msg] holds the next chunk of the message
+	pub fn sign_update(&mut self, ctx: &mut lcr_dilithium_ctx, msg: &[u8]) ->
+		Result<(), SignatureError> {
+		let result = unsafe {
+			leancrypto::lc_dilithium_sign_update(
+				ctx.ctx, msg.as_ptr(), msg.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	/// Complete a streaming signature operation
+	///
+	/// [ctx] holds the context previously passed to [Self::sign_init]
+	pub fn sign_final(&mut self, ctx: &mut lcr_dilithium_ctx) ->
+		Result<(), SignatureError> {
+		if self.sk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sign_final(
+				&mut self.sig, ctx.ctx, &self.sk,
+				leancrypto::lc_seeded_rng)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sig_set = true;
+
+		Ok(())
+	}
+
+	/// Initialize a streaming verification operation
+	///
+	/// [ctx] holds the context used to carry the stream state
+	pub fn verify_init(&mut self, ctx: &mut lcr_dilithium_ctx) ->
+		Result<(), SignatureError> {
+		if self.pk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_verify_init(ctx.ctx, &self.pk)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	/// Feed another chunk of the message into a streaming verification
+	/// operation
+	///
+	/// [ctx] holds the context previously passed to [Self::verify_init]
+	/// [msg] holds the next chunk of the message
+	pub fn verify_update(&mut self, ctx: &mut lcr_dilithium_ctx,
+			      msg: &[u8]) -> Result<(), SignatureError> {
+		let result = unsafe {
+			leancrypto::lc_dilithium_verify_update(
+				ctx.ctx, msg.as_ptr(), msg.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	/// Complete a streaming verification operation
+	///
+	/// [ctx] holds the context previously passed to [Self::verify_init]
+	pub fn verify_final(&mut self, ctx: &mut lcr_dilithium_ctx) ->
+		Result<(), SignatureError> {
+		if self.pk_set == false || self.sig_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_verify_final(
+				&mut self.sig, ctx.ctx, &self.pk)
+		};
+		if result == -1*(leancrypto::EBADMSG as i32) {
+			return Err(SignatureError::VerificationError);
+		}
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
 	/// Method for safe immutable access to signature buffer
 	pub fn sig(&mut self) -> (&[u8], Result<(), SignatureError>) {
 		if self.sig_set == false {
@@ -0,0 +1,309 @@
+/*
+ * Copyright (C) 2025, Stephan Mueller <smueller@chronox.de>
+ *
+ * License: see LICENSE file in root directory
+ *
+ * THIS SOFTWARE IS PROVIDED ``AS IS'' AND ANY EXPRESS OR IMPLIED
+ * WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE, ALL OF
+ * WHICH ARE HEREBY DISCLAIMED.  IN NO EVENT SHALL THE AUTHOR BE
+ * LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+ * CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT
+ * OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR
+ * BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+ * USE OF THIS SOFTWARE, EVEN IF NOT ADVISED OF THE POSSIBILITY OF SUCH
+ * DAMAGE.
+ */
+
+use std::ptr;
+use std::sync::atomic;
+use crate::ffi::leancrypto;
+use crate::error::SignatureError;
+use crate::lcr_dilithium::lcr_dilithium_type;
+
+/// Leancrypto wrapper for lc_dilithium_ed25519
+///
+/// This composite type pairs an ML-DSA key/signature with an Ed25519
+/// key/signature so that a verification only succeeds when both the
+/// post-quantum and the classical component validate.
+pub struct lcr_dilithium_ed25519 {
+	/// Composite public key (ML-DSA + Ed25519 halves)
+	pk: leancrypto::lc_dilithium_ed25519_pk,
+
+	/// Composite secret key (ML-DSA + Ed25519 halves)
+	sk: leancrypto::lc_dilithium_ed25519_sk,
+
+	/// Composite signature (ML-DSA + Ed25519 halves)
+	sig: leancrypto::lc_dilithium_ed25519_sig,
+
+	pk_set: bool,
+	sk_set: bool,
+	sig_set: bool,
+}
+
+#[allow(dead_code)]
+impl lcr_dilithium_ed25519 {
+	pub fn new() -> Self {
+		lcr_dilithium_ed25519 {
+			pk: unsafe { std::mem::zeroed() },
+			sk: unsafe { std::mem::zeroed() },
+			sig: unsafe { std::mem::zeroed() },
+			pk_set: false,
+			sk_set: false,
+			sig_set: false,
+		}
+	}
+
+	/// Load secret key for using with leancrypto
+	///
+	/// [sk_buf] buffer with raw secret key
+	pub fn sk_load(&mut self, sk_buf: &[u8]) -> Result<(), SignatureError> {
+		// No check for self.sk_set == false as we allow overwriting
+		// of existing key.
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_sk_load(&mut self.sk,
+								  sk_buf.as_ptr(),
+								  sk_buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sk_set = true;
+
+		Ok(())
+	}
+
+	/// Load public key for using with leancrypto
+	///
+	/// [pk_buf] buffer with raw public key
+	pub fn pk_load(&mut self, pk_buf: &[u8]) -> Result<(), SignatureError> {
+		// No check for self.pk_set == false as we allow overwriting
+		// of existing key.
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_pk_load(&mut self.pk,
+								  pk_buf.as_ptr(),
+								  pk_buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.pk_set = true;
+
+		Ok(())
+	}
+
+	/// Load signature using with leancrypto
+	///
+	/// [sig_buf] buffer with raw signature
+	pub fn sig_load(&mut self, sig_buf: &[u8]) ->
+		Result<(), SignatureError> {
+		// No check for self.sig_set == false as we allow overwriting
+		// of existing signature.
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_sig_load(
+				&mut self.sig, sig_buf.as_ptr(), sig_buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sig_set = true;
+
+		Ok(())
+	}
+
+	fn lcr_dilithium_type_mapping(dilithium_type: lcr_dilithium_type) ->
+		u32 {
+		match dilithium_type {
+			lcr_dilithium_type::lcr_dilithium_44 =>
+				leancrypto::lc_dilithium_type_LC_DILITHIUM_44,
+			lcr_dilithium_type::lcr_dilithium_65 =>
+				leancrypto::lc_dilithium_type_LC_DILITHIUM_65,
+			lcr_dilithium_type::lcr_dilithium_87 =>
+				leancrypto::lc_dilithium_type_LC_DILITHIUM_87,
+		}
+	}
+
+	/// Generate a combined ML-DSA / Ed25519 key pair
+	///
+	/// [dilithium_type] key type
+	pub fn keypair(&mut self, dilithium_type: lcr_dilithium_type) ->
+		Result<(), SignatureError> {
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_keypair(
+				&mut self.pk, &mut self.sk,
+				leancrypto::lc_seeded_rng,
+				Self::lcr_dilithium_type_mapping(dilithium_type))
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sk_set = true;
+		self.pk_set = true;
+
+		Ok(())
+	}
+
+	/// Sign message with the composite signature operation
+	///
+	/// [msg] holds the message to be signed
+	pub fn sign(&mut self, msg: &[u8]) -> Result<(), SignatureError> {
+		if self.sk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_sign(
+				&mut self.sig, msg.as_ptr(), msg.len(),
+				&self.sk, leancrypto::lc_seeded_rng)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sig_set = true;
+
+		Ok(())
+	}
+
+	/// Deterministically sign message with the composite signature
+	/// operation
+	///
+	/// [msg] holds the message to be signed
+	pub fn sign_deterministic(&mut self, msg: &[u8]) ->
+		Result<(), SignatureError> {
+		if self.sk_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_sign(
+				&mut self.sig, msg.as_ptr(), msg.len(),
+				&self.sk, ptr::null_mut())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		self.sig_set = true;
+
+		Ok(())
+	}
+
+	/// Verify message with the composite signature operation
+	///
+	/// Verification only succeeds if both the ML-DSA and the Ed25519
+	/// component of the signature validate.
+	///
+	/// [msg] holds the message to be verified
+	pub fn verify(&mut self, msg: &[u8]) -> Result<(), SignatureError> {
+		if self.pk_set == false || self.sig_set == false {
+			return Err(SignatureError::UninitializedContext);
+		}
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_verify(
+				&mut self.sig, msg.as_ptr(), msg.len(),
+				&self.pk)
+		};
+		if result == -1*(leancrypto::EBADMSG as i32) {
+			return Err(SignatureError::VerificationError);
+		}
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(())
+	}
+
+	/// Method for safe immutable access to signature buffer
+	pub fn sig(&mut self) -> (&[u8], Result<(), SignatureError>) {
+		if self.sig_set == false {
+			return (&[], Err(SignatureError::UninitializedContext));
+		}
+
+		let mut ptr: *mut u8 = ptr::null_mut();
+		let mut len: usize = 0;
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_sig_ptr(&mut ptr,
+								  &mut len,
+								  &mut self.sig)
+		};
+		if result < 0 {
+			return (&[], Err(SignatureError::ProcessingError));
+		}
+
+		let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+
+		(&slice, Ok(()))
+	}
+
+	/// Method for safe immutable access to secret key buffer
+	pub fn sk(&mut self) -> (&[u8], Result<(), SignatureError>) {
+		if self.sk_set == false {
+			return (&[], Err(SignatureError::UninitializedContext));
+		}
+
+		let mut ptr: *mut u8 = ptr::null_mut();
+		let mut len: usize = 0;
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_sk_ptr(&mut ptr,
+								 &mut len,
+								 &mut self.sk)
+		};
+		if result < 0 {
+			return (&[], Err(SignatureError::ProcessingError));
+		}
+
+		let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+
+		(&slice, Ok(()))
+	}
+
+	/// Method for safe immutable access to public key buffer
+	pub fn pk(&mut self) -> (&[u8], Result<(), SignatureError>) {
+		if self.pk_set == false {
+			return (&[], Err(SignatureError::UninitializedContext));
+		}
+
+		let mut ptr: *mut u8 = ptr::null_mut();
+		let mut len: usize = 0;
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_ed25519_pk_ptr(&mut ptr,
+								 &mut len,
+								 &mut self.pk)
+		};
+		if result < 0 {
+			return (&[], Err(SignatureError::ProcessingError));
+		}
+
+		let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+
+		(&slice, Ok(()))
+	}
+}
+
+/// This ensures the sensitive buffers of both the ML-DSA and the
+/// Ed25519 secret-key half are always zeroized regardless of when it
+/// goes out of scope
+impl Drop for lcr_dilithium_ed25519 {
+	fn drop(&mut self) {
+		let /*mut*/ sk: leancrypto::lc_dilithium_ed25519_sk = unsafe {
+			std::mem::zeroed()
+		};
+
+		unsafe { std::ptr::write_volatile(&mut self.sk, sk) };
+		atomic::compiler_fence(atomic::Ordering::SeqCst);
+	}
+}
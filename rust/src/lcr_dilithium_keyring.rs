@@ -0,0 +1,256 @@
+/*
+ * Copyright (C) 2025, Stephan Mueller <smueller@chronox.de>
+ *
+ * License: see LICENSE file in root directory
+ *
+ * THIS SOFTWARE IS PROVIDED ``AS IS'' AND ANY EXPRESS OR IMPLIED
+ * WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE, ALL OF
+ * WHICH ARE HEREBY DISCLAIMED.  IN NO EVENT SHALL THE AUTHOR BE
+ * LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+ * CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT
+ * OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR
+ * BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+ * USE OF THIS SOFTWARE, EVEN IF NOT ADVISED OF THE POSSIBILITY OF SUCH
+ * DAMAGE.
+ */
+
+use std::collections::HashMap;
+use crate::ffi::leancrypto;
+use crate::error::SignatureError;
+use crate::lcr_dilithium::lcr_dilithium_type;
+
+/// ML-DSA algorithm OID for the IBM Dilithium OID family
+/// (1.3.6.1.4.1.2.267.*), used to restrict a keyring lookup to a
+/// declared parameter set without having to know it up front.
+pub const LCR_DILITHIUM_OID_44: &str = "1.3.6.1.4.1.2.267.7.4.4";
+pub const LCR_DILITHIUM_OID_65: &str = "1.3.6.1.4.1.2.267.7.6.5";
+pub const LCR_DILITHIUM_OID_87: &str = "1.3.6.1.4.1.2.267.7.8.7";
+
+fn lcr_dilithium_type_oid(dilithium_type: &lcr_dilithium_type) -> &'static str {
+	match dilithium_type {
+		lcr_dilithium_type::lcr_dilithium_44 => LCR_DILITHIUM_OID_44,
+		lcr_dilithium_type::lcr_dilithium_65 => LCR_DILITHIUM_OID_65,
+		lcr_dilithium_type::lcr_dilithium_87 => LCR_DILITHIUM_OID_87,
+	}
+}
+
+/// Expected public key wire length for [dilithium_type], used to catch
+/// a caller claiming a parameter set that does not match [pk_buf]
+fn lcr_dilithium_type_pk_len(dilithium_type: &lcr_dilithium_type) -> usize {
+	match dilithium_type {
+		lcr_dilithium_type::lcr_dilithium_44 => 1312,
+		lcr_dilithium_type::lcr_dilithium_65 => 1952,
+		lcr_dilithium_type::lcr_dilithium_87 => 2592,
+	}
+}
+
+/// Digest of a public key's raw bytes, used as the keyring lookup key
+pub type lcr_dilithium_key_id = [u8; 32];
+
+fn lcr_dilithium_key_id_of(pk_buf: &[u8]) -> Result<lcr_dilithium_key_id, SignatureError> {
+	let mut digest = [0u8; 32];
+
+	let result = unsafe {
+		leancrypto::lc_hash(&leancrypto::lc_sha3_256, pk_buf.as_ptr(),
+				    pk_buf.len(), digest.as_mut_ptr())
+	};
+	if result < 0 {
+		return Err(SignatureError::ProcessingError);
+	}
+
+	Ok(digest)
+}
+
+struct lcr_dilithium_keyring_entry {
+	dilithium_type: lcr_dilithium_type,
+	oid: &'static str,
+	pk: leancrypto::lc_dilithium_pk,
+}
+
+/// A verifying keyring dispatching a signature against a set of
+/// trusted Dilithium public keys
+///
+/// This lets a relying party validate an artifact signed by any one
+/// of several trusted keys, possibly of different ML-DSA parameter
+/// sets, without knowing in advance which key produced the signature.
+pub struct lcr_dilithium_keyring {
+	keys: HashMap<lcr_dilithium_key_id, lcr_dilithium_keyring_entry>,
+}
+
+#[allow(dead_code)]
+impl lcr_dilithium_keyring {
+	pub fn new() -> Self {
+		lcr_dilithium_keyring { keys: HashMap::new() }
+	}
+
+	/// Load and insert a public key into the keyring
+	///
+	/// [dilithium_type] parameter set of [pk_buf]
+	/// [pk_buf] buffer with the raw public key
+	///
+	/// `lc_dilithium_pk_load` infers the actual parameter set from
+	/// `pk_buf`'s length alone, so [dilithium_type] is checked against
+	/// that length before the key is trusted with it; otherwise a
+	/// mislabeled key would silently defeat [Self::verify_with_oid]'s
+	/// OID-restricted dispatch.
+	///
+	/// Returns the key id the key was inserted under, derived from a
+	/// digest of [pk_buf], which can later be used with [Self::remove].
+	pub fn insert(&mut self, dilithium_type: lcr_dilithium_type,
+		      pk_buf: &[u8]) ->
+		Result<lcr_dilithium_key_id, SignatureError> {
+		if pk_buf.len() != lcr_dilithium_type_pk_len(&dilithium_type) {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let mut pk: leancrypto::lc_dilithium_pk =
+			unsafe { std::mem::zeroed() };
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_pk_load(&mut pk,
+							  pk_buf.as_ptr(),
+							  pk_buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let key_id = lcr_dilithium_key_id_of(pk_buf)?;
+		let oid = lcr_dilithium_type_oid(&dilithium_type);
+
+		self.keys.insert(key_id, lcr_dilithium_keyring_entry {
+			dilithium_type,
+			oid,
+			pk,
+		});
+
+		Ok(key_id)
+	}
+
+	/// Remove a key previously returned by [Self::insert]
+	pub fn remove(&mut self, key_id: &lcr_dilithium_key_id) -> bool {
+		self.keys.remove(key_id).is_some()
+	}
+
+	/// Verify a signature against every key in the keyring
+	///
+	/// Returns the key id of the first key that validates the
+	/// signature, or [SignatureError::VerificationError] if no key
+	/// in the keyring validates it.
+	pub fn verify(&self, msg: &[u8], sig_buf: &[u8]) ->
+		Result<lcr_dilithium_key_id, SignatureError> {
+		self.verify_filtered(msg, sig_buf, None)
+	}
+
+	/// Verify a signature, restricting the search to keys tagged
+	/// with the given ML-DSA algorithm OID
+	pub fn verify_with_oid(&self, msg: &[u8], sig_buf: &[u8],
+				oid: &str) ->
+		Result<lcr_dilithium_key_id, SignatureError> {
+		self.verify_filtered(msg, sig_buf, Some(oid))
+	}
+
+	/// Shared-access verification dispatch
+	///
+	/// Does not mutate `self`: the loaded [leancrypto::lc_dilithium_sig]
+	/// is a local, and `lc_dilithium_verify` only reads the candidate
+	/// public keys, so a `&self` receiver lets multiple threads verify
+	/// against the same keyring concurrently.
+	fn verify_filtered(&self, msg: &[u8], sig_buf: &[u8],
+			    oid: Option<&str>) ->
+		Result<lcr_dilithium_key_id, SignatureError> {
+		let mut sig: leancrypto::lc_dilithium_sig =
+			unsafe { std::mem::zeroed() };
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sig_load(&mut sig,
+							   sig_buf.as_ptr(),
+							   sig_buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		for (key_id, entry) in self.keys.iter() {
+			if let Some(oid) = oid {
+				if entry.oid != oid {
+					continue;
+				}
+			}
+
+			let result = unsafe {
+				leancrypto::lc_dilithium_verify(
+					&mut sig, msg.as_ptr(), msg.len(),
+					&entry.pk)
+			};
+			if result == 0 {
+				return Ok(*key_id);
+			}
+		}
+
+		Err(SignatureError::VerificationError)
+	}
+
+	/// Parameter set a given key was inserted with
+	pub fn key_type(&self, key_id: &lcr_dilithium_key_id) ->
+		Option<&lcr_dilithium_type> {
+		self.keys.get(key_id).map(|entry| &entry.dilithium_type)
+	}
+
+	/// Algorithm OID a given key was inserted with
+	pub fn key_oid(&self, key_id: &lcr_dilithium_key_id) -> Option<&str> {
+		self.keys.get(key_id).map(|entry| entry.oid)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::lcr_dilithium::lcr_dilithium;
+
+	#[test]
+	fn insert_rejects_type_length_mismatch() {
+		let mut keyring = lcr_dilithium_keyring::new();
+		let mut d = lcr_dilithium::new();
+		d.keypair(lcr_dilithium_type::lcr_dilithium_65).unwrap();
+		let (pk_buf, res) = d.pk();
+		res.unwrap();
+		let pk_buf = pk_buf.to_vec();
+
+		// A genuine ML-DSA-65 key mislabeled as ML-DSA-44 must be
+		// rejected, not silently accepted under the wrong OID.
+		let result = keyring.insert(lcr_dilithium_type::lcr_dilithium_44,
+					     &pk_buf);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn verify_dispatches_by_key_and_oid() {
+		let mut keyring = lcr_dilithium_keyring::new();
+		let mut signer = lcr_dilithium::new();
+		signer.keypair(lcr_dilithium_type::lcr_dilithium_65).unwrap();
+		let (pk_buf, res) = signer.pk();
+		res.unwrap();
+		let pk_buf = pk_buf.to_vec();
+
+		let key_id = keyring.insert(lcr_dilithium_type::lcr_dilithium_65,
+					     &pk_buf).unwrap();
+
+		let msg = b"hello keyring";
+		signer.sign(msg).unwrap();
+		let (sig_buf, res) = signer.sig();
+		res.unwrap();
+		let sig_buf = sig_buf.to_vec();
+
+		assert_eq!(keyring.verify(msg, &sig_buf).unwrap(), key_id);
+		assert_eq!(keyring.verify_with_oid(msg, &sig_buf,
+						    LCR_DILITHIUM_OID_65)
+			   .unwrap(), key_id);
+		assert!(keyring.verify_with_oid(msg, &sig_buf,
+						 LCR_DILITHIUM_OID_44)
+			.is_err());
+	}
+}
@@ -0,0 +1,434 @@
+/*
+ * Copyright (C) 2025, Stephan Mueller <smueller@chronox.de>
+ *
+ * License: see LICENSE file in root directory
+ *
+ * THIS SOFTWARE IS PROVIDED ``AS IS'' AND ANY EXPRESS OR IMPLIED
+ * WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE, ALL OF
+ * WHICH ARE HEREBY DISCLAIMED.  IN NO EVENT SHALL THE AUTHOR BE
+ * LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR
+ * CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT
+ * OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS; OR
+ * BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+ * USE OF THIS SOFTWARE, EVEN IF NOT ADVISED OF THE POSSIBILITY OF SUCH
+ * DAMAGE.
+ */
+
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic;
+use std::convert::TryFrom;
+use crate::ffi::leancrypto;
+use crate::error::SignatureError;
+
+/// Parameter-set marker used to tag the owned key/signature types with
+/// their fixed wire length
+pub trait lcr_dilithium_param_set {
+	const PK_LEN: usize;
+	const SK_LEN: usize;
+	const SIG_LEN: usize;
+
+	/// Tag identifying the parameter set in the serde wire format
+	const TAG: u8;
+}
+
+/// ML-DSA-44 parameter set marker
+pub struct lcr_dilithium_param_44;
+/// ML-DSA-65 parameter set marker
+pub struct lcr_dilithium_param_65;
+/// ML-DSA-87 parameter set marker
+pub struct lcr_dilithium_param_87;
+
+impl lcr_dilithium_param_set for lcr_dilithium_param_44 {
+	const PK_LEN: usize = 1312;
+	const SK_LEN: usize = 2560;
+	const SIG_LEN: usize = 2420;
+	const TAG: u8 = 44;
+}
+
+impl lcr_dilithium_param_set for lcr_dilithium_param_65 {
+	const PK_LEN: usize = 1952;
+	const SK_LEN: usize = 4032;
+	const SIG_LEN: usize = 3309;
+	const TAG: u8 = 65;
+}
+
+impl lcr_dilithium_param_set for lcr_dilithium_param_87 {
+	const PK_LEN: usize = 2592;
+	const SK_LEN: usize = 4896;
+	const SIG_LEN: usize = 4627;
+	const TAG: u8 = 87;
+}
+
+/// Owned, length-checked Dilithium / ML-DSA public key
+///
+/// Type invariant: every `lcr_dilithium_pk` is always fully loaded via
+/// [Self::from_bytes] before it becomes reachable, so `pk` is never in
+/// the not-yet-allocated state `lc_dilithium_pk_ptr` handles on first
+/// use. `as_bytes()` relies on this invariant to call that accessor
+/// through a `*mut` derived from `&self` - if a future constructor adds
+/// a way to obtain an `lcr_dilithium_pk` without going through
+/// [Self::from_bytes] first, that constructor must uphold the same
+/// invariant or `as_bytes()` needs to go back to taking `&mut self`.
+pub struct lcr_dilithium_pk<P: lcr_dilithium_param_set> {
+	pk: leancrypto::lc_dilithium_pk,
+	_marker: PhantomData<P>,
+}
+
+#[allow(dead_code)]
+impl<P: lcr_dilithium_param_set> lcr_dilithium_pk<P> {
+	/// Wire length of a public key for this parameter set
+	pub const LEN: usize = P::PK_LEN;
+
+	/// Validate [buf] and load it as a public key
+	pub fn from_bytes(buf: &[u8]) -> Result<Self, SignatureError> {
+		if buf.len() != Self::LEN {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let mut pk: leancrypto::lc_dilithium_pk =
+			unsafe { std::mem::zeroed() };
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_pk_load(&mut pk, buf.as_ptr(),
+							  buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(lcr_dilithium_pk { pk, _marker: PhantomData })
+	}
+
+	/// Method for safe immutable access to the public key buffer
+	///
+	/// Sound only because of the type invariant documented on
+	/// [lcr_dilithium_pk]: the key is always fully loaded by the time
+	/// it is reachable, so the mutable pointer this hands to
+	/// `lc_dilithium_pk_ptr` never takes the first-use allocation path.
+	pub fn as_bytes(&self) -> Result<&[u8], SignatureError> {
+		let mut ptr: *mut u8 = ptr::null_mut();
+		let mut len: usize = 0;
+
+		let pk = &self.pk as *const leancrypto::lc_dilithium_pk as
+			*mut leancrypto::lc_dilithium_pk;
+		let result = unsafe {
+			leancrypto::lc_dilithium_pk_ptr(&mut ptr, &mut len, pk)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+	}
+}
+
+impl<P: lcr_dilithium_param_set> TryFrom<&[u8]> for lcr_dilithium_pk<P> {
+	type Error = SignatureError;
+
+	fn try_from(buf: &[u8]) -> Result<Self, SignatureError> {
+		Self::from_bytes(buf)
+	}
+}
+
+/// Owned, length-checked Dilithium / ML-DSA signature
+///
+/// Carries the same type invariant as [lcr_dilithium_pk]: always fully
+/// loaded via [Self::from_bytes] before it is reachable, which is what
+/// makes `as_bytes()`'s `&self` receiver sound.
+pub struct lcr_dilithium_sig<P: lcr_dilithium_param_set> {
+	sig: leancrypto::lc_dilithium_sig,
+	_marker: PhantomData<P>,
+}
+
+#[allow(dead_code)]
+impl<P: lcr_dilithium_param_set> lcr_dilithium_sig<P> {
+	/// Wire length of a signature for this parameter set
+	pub const LEN: usize = P::SIG_LEN;
+
+	/// Validate [buf] and load it as a signature
+	pub fn from_bytes(buf: &[u8]) -> Result<Self, SignatureError> {
+		if buf.len() != Self::LEN {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let mut sig: leancrypto::lc_dilithium_sig =
+			unsafe { std::mem::zeroed() };
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sig_load(&mut sig,
+							   buf.as_ptr(),
+							   buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(lcr_dilithium_sig { sig, _marker: PhantomData })
+	}
+
+	/// Method for safe immutable access to the signature buffer
+	///
+	/// Sound for the same reason as [lcr_dilithium_pk::as_bytes]: the
+	/// type invariant on [lcr_dilithium_sig] guarantees this is never
+	/// called before the signature is fully loaded.
+	pub fn as_bytes(&self) -> Result<&[u8], SignatureError> {
+		let mut ptr: *mut u8 = ptr::null_mut();
+		let mut len: usize = 0;
+
+		let sig = &self.sig as *const leancrypto::lc_dilithium_sig as
+			*mut leancrypto::lc_dilithium_sig;
+		let result = unsafe {
+			leancrypto::lc_dilithium_sig_ptr(&mut ptr, &mut len, sig)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+	}
+}
+
+impl<P: lcr_dilithium_param_set> TryFrom<&[u8]> for lcr_dilithium_sig<P> {
+	type Error = SignatureError;
+
+	fn try_from(buf: &[u8]) -> Result<Self, SignatureError> {
+		Self::from_bytes(buf)
+	}
+}
+
+/// Owned, length-checked Dilithium / ML-DSA secret key
+///
+/// Just like [crate::lcr_dilithium::lcr_dilithium], this type zeroizes
+/// its secret-key material on drop. Carries the same type invariant as
+/// [lcr_dilithium_pk]: always fully loaded via [Self::from_bytes]
+/// before it is reachable, which is what makes `as_bytes()`'s `&self`
+/// receiver sound.
+pub struct lcr_dilithium_sk<P: lcr_dilithium_param_set> {
+	sk: leancrypto::lc_dilithium_sk,
+	_marker: PhantomData<P>,
+}
+
+#[allow(dead_code)]
+impl<P: lcr_dilithium_param_set> lcr_dilithium_sk<P> {
+	/// Wire length of a secret key for this parameter set
+	pub const LEN: usize = P::SK_LEN;
+
+	/// Validate [buf] and load it as a secret key
+	pub fn from_bytes(buf: &[u8]) -> Result<Self, SignatureError> {
+		if buf.len() != Self::LEN {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		let mut sk: leancrypto::lc_dilithium_sk =
+			unsafe { std::mem::zeroed() };
+
+		let result = unsafe {
+			leancrypto::lc_dilithium_sk_load(&mut sk, buf.as_ptr(),
+							  buf.len())
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(lcr_dilithium_sk { sk, _marker: PhantomData })
+	}
+
+	/// Method for safe immutable access to the secret key buffer
+	///
+	/// Sound for the same reason as [lcr_dilithium_pk::as_bytes]: the
+	/// type invariant on [lcr_dilithium_sk] guarantees this is never
+	/// called before the secret key is fully loaded.
+	pub fn as_bytes(&self) -> Result<&[u8], SignatureError> {
+		let mut ptr: *mut u8 = ptr::null_mut();
+		let mut len: usize = 0;
+
+		let sk = &self.sk as *const leancrypto::lc_dilithium_sk as
+			*mut leancrypto::lc_dilithium_sk;
+		let result = unsafe {
+			leancrypto::lc_dilithium_sk_ptr(&mut ptr, &mut len, sk)
+		};
+		if result < 0 {
+			return Err(SignatureError::ProcessingError);
+		}
+
+		Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+	}
+}
+
+impl<P: lcr_dilithium_param_set> TryFrom<&[u8]> for lcr_dilithium_sk<P> {
+	type Error = SignatureError;
+
+	fn try_from(buf: &[u8]) -> Result<Self, SignatureError> {
+		Self::from_bytes(buf)
+	}
+}
+
+/// This ensures the sensitive buffer is always zeroized regardless of
+/// when it goes out of scope
+impl<P: lcr_dilithium_param_set> Drop for lcr_dilithium_sk<P> {
+	fn drop(&mut self) {
+		let /*mut*/ sk: leancrypto::lc_dilithium_sk = unsafe {
+			std::mem::zeroed()
+		};
+
+		unsafe { std::ptr::write_volatile(&mut self.sk, sk) };
+		atomic::compiler_fence(atomic::Ordering::SeqCst);
+	}
+}
+
+/// Serde support for the Dilithium key/signature newtypes
+///
+/// Public keys and signatures are encoded as the raw leancrypto-encoded
+/// bytes plus a tag identifying the parameter set, so that
+/// deserialization can restore the correct [lcr_dilithium_param_set]
+/// and reject malformed or wrong-length input. Secret-key serde support
+/// is gated behind the additional "serde-secrets" feature so that
+/// enabling plain "serde" cannot accidentally persist secret material.
+#[cfg(feature = "serde")]
+mod serde_support {
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+	use serde::de::Error as DeError;
+	use serde::ser::Error as SerError;
+	use super::*;
+
+	#[derive(Serialize, Deserialize)]
+	struct RawMaterial {
+		tag: u8,
+		bytes: Vec<u8>,
+	}
+
+	impl<P: lcr_dilithium_param_set> Serialize for lcr_dilithium_pk<P> {
+		fn serialize<S>(&self, serializer: S) ->
+			Result<S::Ok, S::Error> where S: Serializer {
+			let bytes = self.as_bytes().map_err(|_| {
+				S::Error::custom(
+					"failed to access Dilithium public key")
+			})?.to_vec();
+			RawMaterial { tag: P::TAG, bytes }.serialize(serializer)
+		}
+	}
+
+	impl<'de, P: lcr_dilithium_param_set> Deserialize<'de> for
+		lcr_dilithium_pk<P> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+			where D: Deserializer<'de> {
+			let raw = RawMaterial::deserialize(deserializer)?;
+			if raw.tag != P::TAG {
+				return Err(D::Error::custom(
+					"Dilithium parameter-set tag mismatch"));
+			}
+
+			lcr_dilithium_pk::<P>::from_bytes(&raw.bytes)
+				.map_err(|_| D::Error::custom(
+					"invalid Dilithium public key encoding"))
+		}
+	}
+
+	impl<P: lcr_dilithium_param_set> Serialize for lcr_dilithium_sig<P> {
+		fn serialize<S>(&self, serializer: S) ->
+			Result<S::Ok, S::Error> where S: Serializer {
+			let bytes = self.as_bytes().map_err(|_| {
+				S::Error::custom(
+					"failed to access Dilithium signature")
+			})?.to_vec();
+			RawMaterial { tag: P::TAG, bytes }.serialize(serializer)
+		}
+	}
+
+	impl<'de, P: lcr_dilithium_param_set> Deserialize<'de> for
+		lcr_dilithium_sig<P> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+			where D: Deserializer<'de> {
+			let raw = RawMaterial::deserialize(deserializer)?;
+			if raw.tag != P::TAG {
+				return Err(D::Error::custom(
+					"Dilithium parameter-set tag mismatch"));
+			}
+
+			lcr_dilithium_sig::<P>::from_bytes(&raw.bytes)
+				.map_err(|_| D::Error::custom(
+					"invalid Dilithium signature encoding"))
+		}
+	}
+
+	// Secret keys are excluded from serde support unless the caller
+	// has explicitly opted in, since naively deriving this would make
+	// it easy to persist plaintext secret-key material.
+	#[cfg(feature = "serde-secrets")]
+	impl<P: lcr_dilithium_param_set> Serialize for lcr_dilithium_sk<P> {
+		fn serialize<S>(&self, serializer: S) ->
+			Result<S::Ok, S::Error> where S: Serializer {
+			let bytes = self.as_bytes().map_err(|_| {
+				S::Error::custom(
+					"failed to access Dilithium secret key")
+			})?.to_vec();
+			RawMaterial { tag: P::TAG, bytes }.serialize(serializer)
+		}
+	}
+
+	#[cfg(feature = "serde-secrets")]
+	impl<'de, P: lcr_dilithium_param_set> Deserialize<'de> for
+		lcr_dilithium_sk<P> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+			where D: Deserializer<'de> {
+			let raw = RawMaterial::deserialize(deserializer)?;
+			if raw.tag != P::TAG {
+				return Err(D::Error::custom(
+					"Dilithium parameter-set tag mismatch"));
+			}
+
+			lcr_dilithium_sk::<P>::from_bytes(&raw.bytes)
+				.map_err(|_| D::Error::custom(
+					"invalid Dilithium secret key encoding"))
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use crate::lcr_dilithium::{lcr_dilithium, lcr_dilithium_type};
+
+		#[test]
+		fn pk_serde_round_trip() {
+			let mut d = lcr_dilithium::new();
+			d.keypair(lcr_dilithium_type::lcr_dilithium_44).unwrap();
+			let (pk_buf, res) = d.pk();
+			res.unwrap();
+			let pk_buf = pk_buf.to_vec();
+
+			let pk = lcr_dilithium_pk::<lcr_dilithium_param_44>::
+				from_bytes(&pk_buf).unwrap();
+			let json = serde_json::to_string(&pk).unwrap();
+			let pk2: lcr_dilithium_pk<lcr_dilithium_param_44> =
+				serde_json::from_str(&json).unwrap();
+
+			assert_eq!(pk.as_bytes().unwrap(), pk2.as_bytes().unwrap());
+		}
+
+		#[test]
+		fn pk_serde_rejects_parameter_set_tag_mismatch() {
+			let mut d = lcr_dilithium::new();
+			d.keypair(lcr_dilithium_type::lcr_dilithium_44).unwrap();
+			let (pk_buf, res) = d.pk();
+			res.unwrap();
+
+			// Same bytes as a genuine ML-DSA-44 key, but tagged as if
+			// they were ML-DSA-65 - must be rejected, not silently
+			// accepted under the wrong parameter set.
+			let mislabeled = RawMaterial {
+				tag: lcr_dilithium_param_65::TAG,
+				bytes: pk_buf.to_vec(),
+			};
+			let json = serde_json::to_string(&mislabeled).unwrap();
+
+			let result: Result<
+				lcr_dilithium_pk<lcr_dilithium_param_44>, _> =
+				serde_json::from_str(&json);
+			assert!(result.is_err());
+		}
+	}
+}